@@ -0,0 +1,74 @@
+//! Exercises `MaybeFaux::mock_scope`/`MockContext` directly against the
+//! public `Faux`/`SafeCell` API, without going through `faux::create`/
+//! `faux::methods` (which this checkout doesn't have the proc-macro
+//! implementation for). `TypeId::of::<Marker>()` stands in for the
+//! per-method id that macro-generated code would normally pass in, and
+//! `Faux::safe_call_mock` stands in for a macro-generated call site.
+
+use faux::{Faux, MaybeFaux, SafeCell};
+use std::any::TypeId;
+
+struct Get;
+struct GetTwice;
+
+fn cell_of(faux: &MaybeFaux<()>) -> &SafeCell<Faux> {
+    match faux {
+        MaybeFaux::Faux(cell) => cell,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn scoped_stub_is_removed_after_scope() {
+    let faux: MaybeFaux<()> = MaybeFaux::faux();
+
+    faux.mock_scope(|ctx| {
+        ctx.when::<(), i32>(TypeId::of::<Get>()).safe_then(|_| 3);
+    });
+
+    // A fresh scope sees no leftover stub from the previous one: if it
+    // had, this second stub would never answer the call below, since
+    // the still-present old stub (registered first) would match first.
+    faux.mock_scope(|ctx| {
+        ctx.when::<(), i32>(TypeId::of::<Get>()).safe_then(|_| 5);
+        let out: Option<i32> = Faux::safe_call_mock(
+            cell_of(&faux),
+            &TypeId::of::<Get>(),
+            (),
+            Some(Faux::record_call_history::<()>),
+        );
+        assert_eq!(out, Some(5));
+    });
+}
+
+#[test]
+fn reentrant_call_inside_scope_does_not_alias() {
+    let faux: MaybeFaux<()> = MaybeFaux::faux();
+
+    faux.mock_scope(|ctx| {
+        ctx.when::<(), i32>(TypeId::of::<Get>()).safe_then(|_| 10);
+
+        // Both methods below are dispatched on the very same `Faux` from
+        // inside the scope, while `ctx` (and the `MockContext` it wraps)
+        // is still alive. `ctx` only holds a `&SafeCell<Faux>`, so each
+        // dispatch goes through its own short-lived `apply` call instead
+        // of aliasing a borrow held by the context.
+        ctx.when::<(), i32>(TypeId::of::<GetTwice>())
+            .safe_then(|_| 10 + 10);
+
+        let get: Option<i32> = Faux::safe_call_mock(
+            cell_of(&faux),
+            &TypeId::of::<Get>(),
+            (),
+            Some(Faux::record_call_history::<()>),
+        );
+        let get_twice: Option<i32> = Faux::safe_call_mock(
+            cell_of(&faux),
+            &TypeId::of::<GetTwice>(),
+            (),
+            Some(Faux::record_call_history::<()>),
+        );
+        assert_eq!(get, Some(10));
+        assert_eq!(get_twice, Some(20));
+    });
+}
@@ -0,0 +1,61 @@
+//! Exercises predicate-matched stubs (`WhenHolder::with`/`ConfiguredWhen::with`)
+//! directly against the public `Faux` API, standing in for
+//! `when!(foo.get).with(pred).safe_then(...)`.
+
+use faux::{Faux, SafeCell, Times};
+use std::any::TypeId;
+
+struct Get;
+
+#[test]
+fn first_matching_predicate_wins_in_insertion_order() {
+    let cell = SafeCell::new(Faux::default());
+
+    cell.apply(|faux| {
+        faux.safe_mock(
+            TypeId::of::<Get>(),
+            Some(Box::new(|x: &i32| *x > 5)),
+            Times::Always,
+            None,
+            |_: i32| "big",
+        );
+        faux.safe_mock(
+            TypeId::of::<Get>(),
+            Some(Box::new(|x: &i32| *x > 0)),
+            Times::Always,
+            None,
+            |_: i32| "small positive",
+        );
+        faux.safe_mock(TypeId::of::<Get>(), None, Times::Always, None, |_: i32| {
+            "fallback"
+        });
+    });
+
+    assert_eq!(
+        Faux::safe_call_mock::<i32, &str>(
+            &cell,
+            &TypeId::of::<Get>(),
+            10,
+            Some(Faux::record_call_history::<i32>)
+        ),
+        Some("big")
+    );
+    assert_eq!(
+        Faux::safe_call_mock::<i32, &str>(
+            &cell,
+            &TypeId::of::<Get>(),
+            1,
+            Some(Faux::record_call_history::<i32>)
+        ),
+        Some("small positive")
+    );
+    assert_eq!(
+        Faux::safe_call_mock::<i32, &str>(
+            &cell,
+            &TypeId::of::<Get>(),
+            -1,
+            Some(Faux::record_call_history::<i32>)
+        ),
+        Some("fallback")
+    );
+}
@@ -0,0 +1,62 @@
+//! Exercises `MaybeFaux::Spy`/`MockResult::resolve` directly against the
+//! public `Faux` API, standing in for what a macro-generated call site
+//! on a `#[faux::methods]` spy would do: look up a stub, and resolve its
+//! `MockResult` against the real method.
+
+use faux::{Faux, MaybeFaux, MockResult, Times};
+use std::any::TypeId;
+
+struct Get;
+
+fn real_of(faux: &MaybeFaux<i32>) -> i32 {
+    match faux {
+        MaybeFaux::Spy { real, .. } => *real,
+        _ => unreachable!(),
+    }
+}
+
+fn dispatch(faux: &MaybeFaux<i32>) -> i32 {
+    let real = real_of(faux);
+    match faux {
+        MaybeFaux::Spy { faux: cell, .. } => {
+            match Faux::safe_call_mock::<(), MockResult<(), i32>>(
+                cell,
+                &TypeId::of::<Get>(),
+                (),
+                Some(Faux::record_call_history::<()>),
+            ) {
+                Some(result) => result.resolve(|_| real),
+                None => real,
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn spy_falls_through_to_real_on_continue() {
+    let faux = MaybeFaux::spy(3);
+    if let MaybeFaux::Spy { faux: cell, .. } = &faux {
+        cell.apply(|faux| {
+            faux.safe_mock(TypeId::of::<Get>(), None, Times::Always, None, |_: ()| {
+                MockResult::<(), i32>::Continue(())
+            })
+        });
+    }
+
+    assert_eq!(dispatch(&faux), 3);
+}
+
+#[test]
+fn spy_answers_with_stub_on_return() {
+    let faux = MaybeFaux::spy(3);
+    if let MaybeFaux::Spy { faux: cell, .. } = &faux {
+        cell.apply(|faux| {
+            faux.safe_mock(TypeId::of::<Get>(), None, Times::Always, None, |_: ()| {
+                MockResult::<(), i32>::Return(10)
+            })
+        });
+    }
+
+    assert_eq!(dispatch(&faux), 10);
+}
@@ -0,0 +1,60 @@
+//! Exercises `ThreadSafeFaux` directly against the public API, standing
+//! in for what a `#[faux::methods]` call site on a
+//! `MaybeFaux::thread_safe_faux()` mock would do.
+
+use faux::{Faux, ThreadSafeFaux, Times};
+use std::any::TypeId;
+
+struct Get;
+
+#[test]
+fn stub_set_up_before_move_is_honored_on_another_thread() {
+    let faux = ThreadSafeFaux::new();
+    faux.safe_mock(TypeId::of::<Get>(), None, Times::Always, |i: i32| i + 1);
+
+    let handle = std::thread::spawn(move || {
+        let out: Option<i32> =
+            faux.safe_call_mock(&TypeId::of::<Get>(), 41, Some(Faux::record_call_history::<i32>));
+        out
+    });
+
+    assert_eq!(handle.join().unwrap(), Some(42));
+}
+
+#[test]
+fn dropping_the_handle_frees_its_registry_entry() {
+    // Not directly observable from outside the crate, but this at least
+    // confirms a `ThreadSafeFaux` can be created and dropped repeatedly
+    // without anything panicking (e.g. a double-remove on drop).
+    for _ in 0..3 {
+        let faux = ThreadSafeFaux::new();
+        faux.safe_mock(TypeId::of::<Get>(), None, Times::Always, |i: i32| i);
+        drop(faux);
+    }
+}
+
+#[test]
+fn predicate_matching_and_call_limiting_work_through_the_thread_safe_path() {
+    let faux = ThreadSafeFaux::new();
+    faux.safe_mock(
+        TypeId::of::<Get>(),
+        Some(Box::new(|i: &i32| *i > 0)),
+        Times::Times(1),
+        |i: i32| i + 1,
+    );
+
+    let record: Option<fn(&mut Faux, TypeId, &i32)> = Some(Faux::record_call_history::<i32>);
+    assert_eq!(
+        faux.safe_call_mock::<i32, i32>(&TypeId::of::<Get>(), -1, record),
+        None
+    );
+    assert_eq!(
+        faux.safe_call_mock::<i32, i32>(&TypeId::of::<Get>(), 1, record),
+        Some(2)
+    );
+    assert_eq!(
+        faux.safe_call_mock::<i32, i32>(&TypeId::of::<Get>(), 1, record),
+        None
+    );
+    assert_eq!(faux.call_count(&TypeId::of::<Get>()), 3);
+}
@@ -0,0 +1,67 @@
+//! Exercises call recording/`VerifyHolder` directly against the
+//! public API, standing in for `verify!(foo.x)...` and a macro-generated
+//! call site dispatching through `Faux::safe_call_mock`.
+
+use faux::{Faux, MaybeFaux, SafeCell, Times, VerifyHolder};
+use std::any::TypeId;
+use std::marker::PhantomData;
+
+struct Get;
+
+fn verify_holder(faux: &Faux) -> VerifyHolder<'_, i32> {
+    VerifyHolder {
+        id: TypeId::of::<Get>(),
+        name: "get",
+        faux,
+        _marker: PhantomData,
+    }
+}
+
+fn cell_of(faux: &MaybeFaux<()>) -> &SafeCell<Faux> {
+    match faux {
+        MaybeFaux::Faux(cell) => cell,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn call_through_safe_call_mock_is_recorded() {
+    let faux: MaybeFaux<()> = MaybeFaux::faux();
+    let cell = cell_of(&faux);
+
+    cell.apply(|faux| verify_holder(faux).never());
+
+    // No stub matches, but the call must still be recorded.
+    let out: Option<i32> = Faux::safe_call_mock(
+        cell,
+        &TypeId::of::<Get>(),
+        1,
+        Some(Faux::record_call_history::<i32>),
+    );
+    assert_eq!(out, None);
+
+    cell.apply(|faux| {
+        faux.safe_mock(TypeId::of::<Get>(), None, Times::Always, None, |i: i32| {
+            i + 1
+        })
+    });
+    let out: Option<i32> = Faux::safe_call_mock(
+        cell,
+        &TypeId::of::<Get>(),
+        41,
+        Some(Faux::record_call_history::<i32>),
+    );
+    assert_eq!(out, Some(42));
+
+    cell.apply(|faux| verify_holder(faux).called_times(2));
+    cell.apply(|faux| {
+        verify_holder(faux)
+            .with(|input: &i32| *input == 41)
+            .called_times(1)
+    });
+    cell.apply(|faux| {
+        verify_holder(faux)
+            .with(|input: &i32| *input == 7)
+            .never()
+    });
+}